@@ -56,7 +56,10 @@ fn main() {
   // pointer stored on the stack for the string points to the content in the heap
   // the length is how much memory (in bytes) the contents of the String is currently using
   // the capacity is the total amount of memory (in bytes) that the String has received from the OS
-  let s1 = String::from("hello"); // requests allocation on the heap for a new String
+  // s1/s2/s3 are wrapped in Tracked so the moves below print the moment each
+  // one is actually dropped, instead of asking you to take the scoping rules
+  // on faith
+  let s1 = Tracked::new("s1", String::from("hello")); // requests allocation on the heap for a new String
   let s2 = s1; // Content is not copied only the data pointing to the same content is copied
                // Pointer, length and capacity data from s1 is copied to a new variable called s2 and stores the same data as s1 on the stack
                // if s1 and s2 try to go out of memory at the same time it will try to free the same data and will cause a double free error
@@ -69,10 +72,13 @@ fn main() {
   println!("{} {} {} {}", s2, s3, x, y); // s1 is the only one that wont work because it was "moved"
                                          // x works because integers with a fixed value and size (i32) are stored on the stack and deep copies are quick to make (doesn't invalidate x and both are dropped at the end of the scope)
   take_ownership(s3); // s3's value moves into the function and is no longer valid here
-                      // println!("{}", s3); // this is invalid because s3 was just moved
+                      // s3 was cloned from s2, so Tracked named it "s1 (clone)"; the
+                      // "dropping s1 (clone)" that prints next comes from inside
+                      // take_ownership, not here, because s3 was moved into it
   make_copy(x); // x's value moves into the function but i32 is Copy so it's okay to still use x afterward
                 // println!("{}", x); // this is valid because i32 is Copy and isn't moved
   let s3 = take_and_give_back(s2); // s2 is moved into the function and the return value is moved into s3
+                                   // nothing prints for s2 here: it wasn't dropped, it was returned and is still alive as s3
   println!("{}", s3);
   let (s2, s3) = return_multiple_values(s3); // ownership of s3 is sent to the function and the function returns a tuple with multiple Strings that are stored on the new into new variables
   println!("{} {}", s2, s3); // both s2 and s3 are valid now
@@ -91,6 +97,58 @@ fn main() {
   let world = &s[6..11]; // internally the slice data structure stores the starting position and length of the slice which corresponds to the ending_index minus starting_index
                          // .. is range syntax start..end (if you leave out start or end it will start at the first index 0 to value or value to end (..2 == 0..2 && 2.. == 2..102))
   println!("{}, {}!", hello, world);
+
+  // first_word only ever hands back the word at index 0; nth_word/last_word/words
+  // are the same byte-scanning slice over the rest of the string
+  println!("{:?}", nth_word(&s, 1)); // Some("world")
+  println!("{:?}", nth_word(&s, 5)); // None, there is no sixth word
+  println!("{}", last_word(&s)); // "world"
+  println!("{}", last_word("   ")); // "", an all-separator string has no words
+  for word in words(&s) {
+    println!("word: {}", word);
+  }
+
+  // slices aren't only for &str; &[T] works the same way
+  let a = [1, 2, 3, 4, 5];
+  println!("{:?}", slice::first(&a)); // Some(1)
+  println!("{:?}", slice::chunk(&a, 1, 3)); // [2, 3, 4]
+  println!("{:?}", slice::split_at_value(&a, &3)); // ([1, 2], [4, 5])
+
+  // mem_model turns the (ptr, len, cap) explanation above into runtime state
+  // you can actually print, instead of just reading about it
+  let mut arena = mem_model::HeapArena::new();
+  let mut sim = mem_model::SimString::from_str(&mut arena, "hi");
+  print!("{}", arena.snapshot());
+  sim.push_str(&mut arena, ", there"); // outgrows the original capacity, so this reallocates
+  println!("{}", sim.as_str(&arena));
+  print!("{}", arena.snapshot());
+  let sim_clone = sim.clone(&mut arena); // a new region with its own copy of the bytes
+  print!("{}", arena.snapshot());
+  let sim_moved = sim.move_to(); // same stack record, new binding; nothing changes on the heap
+  sim_moved.drop(&mut arena).unwrap();
+  sim_clone.drop(&mut arena).unwrap();
+  print!("{}", arena.snapshot()); // both regions now show up as freed
+
+  // BorrowCell makes the "one &mut XOR many &" rule something you can break
+  // at runtime and see, instead of something the compiler just never lets you try
+  let cell = borrow_cell::BorrowCell::new(5);
+  let r1 = cell.borrow().unwrap();
+  let r2 = cell.borrow().unwrap(); // two shared borrows are fine
+  println!("{} {}", *r1, *r2);
+  match cell.borrow_mut() {
+    Ok(_) => println!("unexpectedly got a mutable borrow"),
+    Err(e) => println!("{}", e), // "cannot take &mut while 2 shared borrow(s) are active"
+  }
+  drop(r1);
+  drop(r2); // now there are zero readers, so a mutable borrow succeeds
+  let mut w = cell.borrow_mut().unwrap();
+  *w += 1;
+  match cell.borrow() {
+    Ok(_) => println!("unexpectedly got a shared borrow"),
+    Err(e) => println!("{}", e), // "cannot take & while a mutable borrow is active"
+  }
+  drop(w);
+  println!("{}", *cell.borrow().unwrap());
 } // now s isn't dropped because it was moved but x is still dropped
   // this scope is now over and s is no longer valid and the memory for our String type is returned to the OS
   // we know the contents of string literals at compile time so the text is hard coded directly into the final executable
@@ -112,18 +170,21 @@ fn main() {
 // The character type, char.
 // Tuples, if they only contain types that are also Copy. For example, (i32, i32) is Copy, but (i32, String) is not.
 
-fn take_ownership(a_string: String) {
+fn take_ownership(a_string: Tracked<String>) {
   // a_string comes into scope
   println!("{}", a_string);
 } // a_string goes out of scope and drop is called
   // memory is then freed
+  // (this is also where the "dropping s1 (clone)" for the value moved in as
+  // s3 actually prints, proving the drop happens here in the callee and not
+  // back in main)
 
 fn make_copy(a_int: i32) {
   // a_int comes into scope
   println!("{}", a_int);
 } // a_int goes out of scope and nothing happens because ownership is restored (Copy type)
 
-fn take_and_give_back(a_string: String) -> String {
+fn take_and_give_back(a_string: Tracked<String>) -> Tracked<String> {
   a_string // a_string is returned and moved out to the calling function
 }
 // these are examples of ownership being transferred between functions
@@ -132,12 +193,63 @@ fn take_and_give_back(a_string: String) -> String {
 // the value will be cleaned up by drop
 // unless the data has been moved to be owned by another variable
 
-fn return_multiple_values(a_string: String) -> (String, String) {
+fn return_multiple_values(a_string: Tracked<String>) -> (Tracked<String>, Tracked<String>) {
   let another_string = a_string.clone(); // deep cloned into a new string variable
   (another_string, a_string) // returns a tuple with the new cloned string and the old string
                              // ownership of both the new and old string are returned to the calling function
 }
 
+// wraps a value so that dropping it is something you can actually see happen.
+// the comments above describe drop running at the end of a block and a moved
+// value being dropped by whoever it was moved into, but none of that shows up
+// in the program's output unless something logs it on the way out
+struct Tracked<T> {
+  name: String,
+  payload: T,
+}
+
+impl<T> Tracked<T> {
+  fn new(name: &str, payload: T) -> Self {
+    Tracked { name: name.to_string(), payload }
+  }
+}
+
+impl<T: Clone> Tracked<T> {
+  // a deep clone, same as calling .clone() on the payload directly, but given
+  // a name of its own so its "dropping ..." line can be told apart from the
+  // value it was cloned from instead of printing an identical line
+  fn clone(&self) -> Self {
+    Tracked {
+      name: format!("{} (clone)", self.name),
+      payload: self.payload.clone(),
+    }
+  }
+}
+
+// lets a Tracked<T> be used wherever a &T is expected (e.g. s.len()) without
+// unwrapping it by hand every time
+impl<T> std::ops::Deref for Tracked<T> {
+  type Target = T;
+  fn deref(&self) -> &T {
+    &self.payload
+  }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Tracked<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.payload)
+  }
+}
+
+impl<T> Drop for Tracked<T> {
+  fn drop(&mut self) {
+    // this is the "drop" the comments keep mentioning, made visible: it runs
+    // wherever the value's scope actually ends, which is often not where it
+    // was created
+    println!("dropping {}", self.name);
+  }
+}
+
 // Because all of this is a pain in the ass
 // Rust has a feature called references
 // Passing a reference to a object instead of taking ownership is much more effective
@@ -226,3 +338,389 @@ fn first_word(s: &str) -> &str {
 // let a = [1, 2, 3, 4, 5]
 // &a[1..3] <-- this is a slice as well but not a string slice
 // works in the same way as a string but is for more general data types such as collections
+
+// first_word only ever hands back the first token, so anything that needs the
+// second word, the last word, or just wants to walk every word has to reinvent
+// the same byte-scanning loop. these do the same scanning but are general
+// enough to cover all of those cases while still returning slices that borrow
+// from the original &str (so the same "clear the string and the borrow checker
+// yells at you" lesson still applies)
+
+// returns the nth (0-indexed) space-delimited word in the string, if it exists
+fn nth_word(s: &str, n: usize) -> Option<&str> {
+  let bytes = s.as_bytes();
+  let mut start = 0;
+  let mut count = 0;
+
+  let mut i = 0;
+  while i <= bytes.len() {
+    // a word ends either at a space or at the end of the string
+    if i == bytes.len() || bytes[i] == b' ' {
+      if start < i {
+        if count == n {
+          return Some(&s[start..i]);
+        }
+        count += 1;
+      }
+      start = i + 1;
+    }
+    i += 1;
+  }
+
+  None
+}
+
+// returns the last space-delimited word in the string
+fn last_word(s: &str) -> &str {
+  let bytes = s.as_bytes();
+  let mut start = 0;
+  let mut last_start = 0;
+  let mut last_end = 0; // no token found yet; stays 0 for an empty or all-separator string
+
+  for (i, &item) in bytes.iter().enumerate() {
+    if item == b' ' {
+      if start < i {
+        last_start = start;
+        last_end = i;
+      }
+      start = i + 1;
+    }
+  }
+
+  // the final token (after the last space, or the whole string if there's no space)
+  if start < bytes.len() {
+    last_start = start;
+    last_end = bytes.len();
+  }
+
+  &s[last_start..last_end]
+}
+
+// returns an iterator over every space-delimited word, each still a slice
+// borrowed from the original string. built on the same start/end byte
+// scanning as nth_word and last_word rather than str::split so the whole
+// family stays consistent
+fn words(s: &str) -> impl Iterator<Item = &str> {
+  let bytes = s.as_bytes();
+  let mut start = 0;
+  let mut i = 0;
+
+  std::iter::from_fn(move || {
+    while i <= bytes.len() {
+      if i == bytes.len() || bytes[i] == b' ' {
+        let word_start = start;
+        let word_end = i;
+        start = i + 1;
+        i += 1;
+        if word_start < word_end {
+          return Some(&s[word_start..word_end]);
+        }
+        continue;
+      }
+      i += 1;
+    }
+    None
+  })
+}
+
+// the comments above point out that &a[1..3] slices an array the same way a
+// string slice works, but only the string side of that was ever implemented.
+// this module is the generic counterpart: the same "borrow a piece of the
+// source, don't copy it" slicing, but over &[T] instead of &str
+mod slice {
+  // returns a reference to the first element of the slice, or None if it's empty
+  pub fn first<T>(s: &[T]) -> Option<&T> {
+    if s.is_empty() {
+      None
+    } else {
+      Some(&s[0])
+    }
+  }
+
+  // returns the subslice s[start..start + len]
+  // panics the same way indexing a slice out of bounds normally does
+  pub fn chunk<T>(s: &[T], start: usize, len: usize) -> &[T] {
+    &s[start..start + len]
+  }
+
+  // splits the slice around the first element equal to sep, returning the
+  // parts before and after it (sep itself is included in neither half)
+  // if sep isn't found the whole slice is returned as the first half and the
+  // second half is empty
+  pub fn split_at_value<'a, T: PartialEq>(s: &'a [T], sep: &T) -> (&'a [T], &'a [T]) {
+    for (i, item) in s.iter().enumerate() {
+      if item == sep {
+        return (&s[..i], &s[i + 1..]);
+      }
+    }
+    (s, &s[s.len()..])
+  }
+}
+
+// everything above this point just exercises the rules the comments describe
+// (moves, clones, drops happen but you only ever see the result, not the
+// memory underneath). this module makes the (ptr, len, cap) stack record and
+// the heap it points at into real runtime data you can print and inspect
+mod mem_model {
+  use std::collections::HashMap;
+
+  // stands in for a heap pointer; a real String holds a *mut u8, but we hand
+  // out small handles here so the "heap" can live in a plain HashMap
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+  pub struct HeapId(usize);
+
+  struct HeapRegion {
+    bytes: Vec<u8>,
+    cap: usize,
+    in_use: bool,
+  }
+
+  // stands in for the OS: hands out addresses for new allocations and keeps
+  // track of which ones are currently marked in use
+  pub struct HeapArena {
+    regions: HashMap<usize, HeapRegion>,
+    next_id: usize,
+  }
+
+  impl HeapArena {
+    pub fn new() -> Self {
+      HeapArena {
+        regions: HashMap::new(),
+        next_id: 0,
+      }
+    }
+
+    // finds an empty spot and marks it as being in use, the same way the
+    // comments at the top of the file describe allocating on the heap
+    fn alloc(&mut self, bytes: Vec<u8>, cap: usize) -> HeapId {
+      let id = self.next_id;
+      self.next_id += 1;
+      self.regions.insert(
+        id,
+        HeapRegion {
+          bytes,
+          cap,
+          in_use: true,
+        },
+      );
+      HeapId(id)
+    }
+
+    fn region(&self, id: HeapId) -> &HeapRegion {
+      self.regions.get(&id.0).expect("HeapId does not refer to a region in this arena")
+    }
+
+    fn region_mut(&mut self, id: HeapId) -> &mut HeapRegion {
+      self.regions.get_mut(&id.0).expect("HeapId does not refer to a region in this arena")
+    }
+
+    // marks a region as freed; errors instead of panicking if it was already
+    // freed, since a double free is exactly the bug ownership exists to prevent
+    fn free(&mut self, id: HeapId) -> Result<(), String> {
+      let region = self.region_mut(id);
+      if !region.in_use {
+        return Err(format!("double free: heap address {} was already freed", id.0));
+      }
+      region.in_use = false;
+      Ok(())
+    }
+
+    // prints every region this arena has ever handed out, so a move, clone,
+    // or drop can be watched as it changes the heap
+    pub fn snapshot(&self) -> String {
+      let mut ids: Vec<_> = self.regions.keys().collect();
+      ids.sort();
+      let mut out = String::from("heap:\n");
+      for id in ids {
+        let region = &self.regions[id];
+        out.push_str(&format!(
+          "  [{}] {:?} (len {}, cap {}, {})\n",
+          id,
+          String::from_utf8_lossy(&region.bytes),
+          region.bytes.len(),
+          region.cap,
+          if region.in_use { "in use" } else { "freed" }
+        ));
+      }
+      out
+    }
+  }
+
+  // mirrors a real String's stack record: pointer, length and capacity,
+  // with the actual bytes living out in the HeapArena instead of on the stack
+  pub struct SimString {
+    ptr: HeapId,
+    len: usize,
+    cap: usize,
+  }
+
+  impl SimString {
+    // requests an allocation on the heap sized exactly to fit s
+    pub fn from_str(arena: &mut HeapArena, s: &str) -> Self {
+      let bytes = s.as_bytes().to_vec();
+      let cap = bytes.len();
+      let ptr = arena.alloc(bytes, cap);
+      SimString { ptr, len: cap, cap }
+    }
+
+    // appends to the string, growing (reallocating) the backing region when
+    // the new contents no longer fit in the current capacity
+    pub fn push_str(&mut self, arena: &mut HeapArena, s: &str) {
+      let needed = self.len + s.len();
+      if needed > self.cap {
+        let mut bytes = arena.region(self.ptr).bytes[..self.len].to_vec();
+        bytes.extend_from_slice(s.as_bytes());
+        let new_cap = needed;
+        let new_ptr = arena.alloc(bytes, new_cap);
+        arena.free(self.ptr).expect("push_str only ever grows out of a region it still owns");
+        self.ptr = new_ptr;
+        self.cap = new_cap;
+      } else {
+        let region = arena.region_mut(self.ptr);
+        region.bytes.truncate(self.len);
+        region.bytes.extend_from_slice(s.as_bytes());
+      }
+      self.len = needed;
+    }
+
+    // moves the stack record into a new SimString. taking self by value means
+    // the binding this was called on can't be used again afterward, the same
+    // way `let s2 = s1;` invalidates s1 for a real String
+    pub fn move_to(self) -> SimString {
+      self
+    }
+
+    // a deep copy: allocates a fresh region and copies the heap bytes into it,
+    // rather than just duplicating the (ptr, len, cap) stack record
+    pub fn clone(&self, arena: &mut HeapArena) -> Self {
+      let bytes = arena.region(self.ptr).bytes[..self.len].to_vec();
+      let cap = bytes.len();
+      let ptr = arena.alloc(bytes, cap);
+      SimString { ptr, len: self.len, cap }
+    }
+
+    // returns the region to the arena; errors instead of panicking on a
+    // double free so callers can observe the failure instead of crashing
+    pub fn drop(self, arena: &mut HeapArena) -> Result<(), String> {
+      arena.free(self.ptr)
+    }
+
+    pub fn as_str<'a>(&self, arena: &'a HeapArena) -> &'a str {
+      std::str::from_utf8(&arena.region(self.ptr).bytes[..self.len]).expect("SimString bytes are always valid utf8")
+    }
+  }
+}
+
+// the reference rules above (one &mut XOR many &, references must stay
+// valid, no data races) are all enforced at compile time, so there's no way
+// to actually see a violation happen: the program just doesn't compile.
+// BorrowCell moves the same bookkeeping the borrow checker does into a
+// runtime check, so breaking the rules is an Err you can print and inspect
+// instead of a compiler error you never see
+mod borrow_cell {
+  use std::cell::{RefCell, UnsafeCell};
+  use std::fmt;
+
+  // describes which rule was broken and why, so callers can tell a reader
+  // conflict apart from a writer conflict
+  #[derive(Debug, PartialEq, Eq)]
+  pub struct BorrowError(String);
+
+  impl fmt::Display for BorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(f, "{}", self.0)
+    }
+  }
+
+  // readers counts the shared (&) borrows currently active, and writer is true
+  // while a single exclusive (&mut) borrow is active; both at 0/false means
+  // the value is not borrowed
+  struct BorrowState {
+    readers: usize,
+    writer: bool,
+  }
+
+  pub struct BorrowCell<T> {
+    value: UnsafeCell<T>,
+    state: RefCell<BorrowState>,
+  }
+
+  impl<T> BorrowCell<T> {
+    pub fn new(value: T) -> Self {
+      BorrowCell {
+        value: UnsafeCell::new(value),
+        state: RefCell::new(BorrowState { readers: 0, writer: false }),
+      }
+    }
+
+    // hands out a shared guard as long as no &mut borrow is currently active
+    pub fn borrow(&self) -> Result<Ref<'_, T>, BorrowError> {
+      let mut state = self.state.borrow_mut();
+      if state.writer {
+        return Err(BorrowError("cannot take & while a mutable borrow is active".to_string()));
+      }
+      state.readers += 1;
+      Ok(Ref { cell: self })
+    }
+
+    // hands out an exclusive guard only when there are zero readers and zero writers
+    pub fn borrow_mut(&self) -> Result<RefMut<'_, T>, BorrowError> {
+      let mut state = self.state.borrow_mut();
+      if state.writer {
+        return Err(BorrowError("cannot take &mut while another mutable borrow is active".to_string()));
+      }
+      if state.readers > 0 {
+        return Err(BorrowError(format!(
+          "cannot take &mut while {} shared borrow(s) are active",
+          state.readers
+        )));
+      }
+      state.writer = true;
+      Ok(RefMut { cell: self })
+    }
+  }
+
+  // a shared borrow guard; decrements the reader count when it goes out of
+  // scope, the same way a real reference's lifetime ends at the end of its scope
+  pub struct Ref<'a, T> {
+    cell: &'a BorrowCell<T>,
+  }
+
+  impl<'a, T> std::ops::Deref for Ref<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+      // safe: borrow_mut() on self.cell.value is never taken while any Ref is alive
+      unsafe { &*self.cell.value.get() }
+    }
+  }
+
+  impl<'a, T> Drop for Ref<'a, T> {
+    fn drop(&mut self) {
+      self.cell.state.borrow_mut().readers -= 1;
+    }
+  }
+
+  // an exclusive borrow guard; clears the writer flag when it goes out of scope
+  pub struct RefMut<'a, T> {
+    cell: &'a BorrowCell<T>,
+  }
+
+  impl<'a, T> std::ops::Deref for RefMut<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+      unsafe { &*self.cell.value.get() }
+    }
+  }
+
+  impl<'a, T> std::ops::DerefMut for RefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+      unsafe { &mut *self.cell.value.get() }
+    }
+  }
+
+  impl<'a, T> Drop for RefMut<'a, T> {
+    fn drop(&mut self) {
+      self.cell.state.borrow_mut().writer = false;
+    }
+  }
+}